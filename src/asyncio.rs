@@ -0,0 +1,105 @@
+//! An `AsyncRead` adapter that computes a `DropboxContentHasher` digest as bytes flow
+//! through it, so callers streaming a file into an upload don't need a second pass to
+//! hash it.
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use digest::generic_array::GenericArray;
+use digest::{FixedOutput, Input};
+use pin_project::pin_project;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::DropboxContentHasher;
+
+/// Wraps an `AsyncRead` and feeds every byte that passes through it into a
+/// `DropboxContentHasher`, so the content hash is ready as soon as the wrapped reader has
+/// been fully consumed.
+///
+/// ```no_run
+/// use dropbox_content_hasher::asyncio::HashingReader;
+///
+/// # async fn example() -> std::io::Result<()> {
+/// let file = tokio::fs::File::open("some/path").await?;
+/// let mut hashing = HashingReader::new(file);
+/// tokio::io::copy(&mut hashing, &mut tokio::io::sink()).await?;
+/// let (digest, size) = hashing.finalize();
+/// # Ok(())
+/// # }
+/// ```
+#[pin_project]
+#[derive(Debug)]
+pub struct HashingReader<R> {
+    #[pin]
+    inner: R,
+    state: HasherState,
+}
+
+#[derive(Debug)]
+struct HasherState {
+    hasher: DropboxContentHasher,
+    bytes_read: u64,
+}
+
+impl<R> HashingReader<R> {
+    /// Wrap `inner`, starting from a fresh hasher.
+    pub fn new(inner: R) -> Self {
+        HashingReader {
+            inner,
+            state: HasherState {
+                hasher: DropboxContentHasher::new(),
+                bytes_read: 0,
+            },
+        }
+    }
+
+    /// Consume the adapter, returning the content hash digest and the total number of bytes
+    /// that were read through it.
+    pub fn finalize(self) -> (GenericArray<u8, <DropboxContentHasher as FixedOutput>::OutputSize>, u64) {
+        (self.state.hasher.fixed_result(), self.state.bytes_read)
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for HashingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.project();
+        let filled_before = buf.filled().len();
+        let poll = this.inner.poll_read(cx, buf);
+        if poll.is_ready() {
+            let newly_filled = &buf.filled()[filled_before..];
+            if !newly_filled.is_empty() {
+                Input::input(&mut this.state.hasher, newly_filled);
+                this.state.bytes_read += newly_filled.len() as u64;
+            }
+        }
+        poll
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test]
+    async fn matches_sync_hash() {
+        let file = tokio::fs::File::open("test-data/milky-way-nasa.jpg")
+            .await
+            .expect("Couldn't open test file");
+        let mut hashing = HashingReader::new(file);
+
+        let mut sink = Vec::new();
+        hashing.read_to_end(&mut sink).await.expect("read_to_end");
+        let (digest, size) = hashing.finalize();
+
+        let expected =
+            DropboxContentHasher::hash_file("test-data/milky-way-nasa.jpg").expect("sync hash");
+        assert_eq!(digest, expected);
+        assert_eq!(size as usize, sink.len());
+    }
+}
@@ -0,0 +1,170 @@
+//! A strict, comparable representation of a Dropbox `content_hash` value, plus helpers
+//! to verify a file or reader against an expected hash without leaking timing
+//! information about where the comparison diverges.
+
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use subtle::ConstantTimeEq;
+
+use crate::DropboxContentHasher;
+
+/// The number of bytes in a Dropbox content hash (a raw SHA-256 digest).
+const HASH_LEN: usize = 32;
+
+/// A parsed, validated Dropbox `content_hash` value.
+///
+/// Unlike a bare `String`, constructing a `ContentHash` guarantees the value is exactly
+/// 64 lowercase hex characters decoding to 32 bytes, so it can be compared and stored
+/// without re-validating it at every use site.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContentHash([u8; HASH_LEN]);
+
+impl ContentHash {
+    /// Wrap a raw 32-byte digest, such as the output of `DropboxContentHasher::hash_file`.
+    pub fn from_bytes(bytes: [u8; HASH_LEN]) -> Self {
+        ContentHash(bytes)
+    }
+
+    /// Parse a strict lowercase hex `content_hash`, rejecting anything that isn't exactly
+    /// 64 lowercase hex characters (wrong length, uppercase, whitespace, or other stray
+    /// characters are all errors).
+    pub fn from_hex(s: &str) -> Result<Self, ParseContentHashError> {
+        if s.len() != HASH_LEN * 2 {
+            return Err(ParseContentHashError::WrongLength(s.len()));
+        }
+        if !s.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)) {
+            return Err(ParseContentHashError::InvalidHexChar);
+        }
+
+        let mut bytes = [0u8; HASH_LEN];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16)
+                .map_err(|_| ParseContentHashError::InvalidHexChar)?;
+        }
+        Ok(ContentHash(bytes))
+    }
+
+    /// The raw 32-byte digest.
+    pub fn as_bytes(&self) -> &[u8; HASH_LEN] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ContentHash({})", self)
+    }
+}
+
+impl fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ContentHash {
+    type Err = ParseContentHashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ContentHash::from_hex(s)
+    }
+}
+
+/// An error parsing a `content_hash` string into a `ContentHash`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseContentHashError {
+    /// The string wasn't exactly 64 bytes long (carries its actual length in bytes).
+    WrongLength(usize),
+    /// The string contained a character outside `[0-9a-f]`.
+    InvalidHexChar,
+}
+
+impl fmt::Display for ParseContentHashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseContentHashError::WrongLength(len) => {
+                write!(f, "content hash must be exactly {} hex characters, got {} bytes", HASH_LEN * 2, len)
+            }
+            ParseContentHashError::InvalidHexChar => {
+                write!(f, "content hash must be lowercase hex characters only")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseContentHashError {}
+
+/// Hash `path` and compare the result against `expected` in constant time, so that
+/// verifying a downloaded file's content hash doesn't leak timing information about
+/// where the actual and expected digests first differ.
+pub fn verify_file<T>(path: T, expected: &ContentHash) -> io::Result<bool>
+where
+    T: AsRef<Path>,
+{
+    let result = DropboxContentHasher::hash_file(path)?;
+    Ok(bool::from(result.as_slice().ct_eq(expected.as_bytes())))
+}
+
+/// Hash the contents of `reader` and compare the result against `expected` in constant
+/// time. See `verify_file`.
+pub fn verify_reader<T>(reader: T, expected: &ContentHash) -> io::Result<bool>
+where
+    T: Read,
+{
+    let result = DropboxContentHasher::hash_reader(reader)?;
+    Ok(bool::from(result.as_slice().ct_eq(expected.as_bytes())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPECTED_HEX: &str = "485291fa0ee50c016982abbfa943957bcd231aae0492ccbaa22c58e3997b35e0";
+
+    #[test]
+    fn round_trips_through_hex() {
+        let hash = ContentHash::from_hex(EXPECTED_HEX).expect("valid hex");
+        assert_eq!(format!("{:x}", hash), EXPECTED_HEX);
+    }
+
+    #[test]
+    fn rejects_wrong_length() {
+        assert_eq!(
+            ContentHash::from_hex("abcd"),
+            Err(ParseContentHashError::WrongLength(4))
+        );
+    }
+
+    #[test]
+    fn rejects_uppercase_and_stray_characters() {
+        assert_eq!(
+            ContentHash::from_hex(&EXPECTED_HEX.to_uppercase()),
+            Err(ParseContentHashError::InvalidHexChar)
+        );
+        assert_eq!(
+            ContentHash::from_hex(&EXPECTED_HEX.replace('a', "/")),
+            Err(ParseContentHashError::InvalidHexChar)
+        );
+    }
+
+    #[test]
+    fn verify_file_matches_expected_hash() {
+        let expected = ContentHash::from_hex(EXPECTED_HEX).expect("valid hex");
+
+        let matches = verify_file("test-data/milky-way-nasa.jpg", &expected).expect("hash file");
+        assert!(matches);
+    }
+}
@@ -3,15 +3,19 @@
 
 use digest;
 
-use digest::generic_array::typenum::U64;
 use digest::generic_array::GenericArray;
-use digest::{Reset, Digest, FixedOutput, Input};
+use digest::{BlockInput, Reset, Digest, FixedOutput, Input};
 use sha2::Sha256;
 
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
+mod parallel;
+pub mod asyncio;
+pub mod content_hash;
+mod multihash;
+
 pub const BLOCK_SIZE: usize = 4 * 1024 * 1024;
 
 /// Computes a hash using the same algorithm that the Dropbox API uses for the
@@ -21,25 +25,33 @@ pub const BLOCK_SIZE: usize = 4 * 1024 * 1024;
 /// raw binary representation of the hash.  The "content_hash" field in the
 /// Dropbox API is a hexadecimal-encoded version of this value.
 ///
+/// Generic over the inner digest `D`, which defaults to `Sha256` to match the Dropbox
+/// API. The block/overall two-level construction is unchanged for any `D`; only the
+/// primitive used to hash each block and the block list is swapped out, so e.g.
+/// `DropboxContentHasher<Sha512Trunc256>` computes the same style of hash over a
+/// different primitive for callers who don't need Dropbox-compatible output.
+///
 /// For examples see `hash_file` and `hash_reader`, for an using this object directly see the
 /// source of `hash_reader`.
 
 #[derive(Clone, Debug)]
-pub struct DropboxContentHasher {
-    overall_hasher: Sha256,
-    block_hasher: Sha256,
+pub struct DropboxContentHasher<D: Digest + Clone + BlockInput + Input = Sha256> {
+    overall_hasher: D,
+    block_hasher: D,
     block_pos: usize,
 }
 
-impl DropboxContentHasher {
+impl<D: Digest + Clone + BlockInput + Input> DropboxContentHasher<D> {
     pub fn new() -> Self {
         DropboxContentHasher {
-            overall_hasher: Sha256::new(),
-            block_hasher: Sha256::new(),
+            overall_hasher: D::new(),
+            block_hasher: D::new(),
             block_pos: 0,
         }
     }
+}
 
+impl DropboxContentHasher {
     /// Return the content_hash for a given file, or an io::Error from either opening or reading
     /// the file.
     ///
@@ -73,7 +85,7 @@ impl DropboxContentHasher {
     /// ```
     pub fn hash_reader<T>(mut reader: T) -> std::io::Result<GenericArray<u8, <Self as FixedOutput>::OutputSize>>
     where T: Read {
-        let mut hasher = DropboxContentHasher::new();
+        let mut hasher = DropboxContentHasher::<Sha256>::new();
         let mut buf = vec![0; BLOCK_SIZE];
         loop {
             let len = reader.read(&mut buf)?;
@@ -84,28 +96,28 @@ impl DropboxContentHasher {
     }
 }
 
-impl Default for DropboxContentHasher {
+impl<D: Digest + Clone + BlockInput + Input> Default for DropboxContentHasher<D> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Reset for DropboxContentHasher {
+impl<D: Digest + Clone + BlockInput + Input> Reset for DropboxContentHasher<D> {
     fn reset(&mut self) {
-        self.overall_hasher = Sha256::new();
-        self.block_hasher = Sha256::new();
+        self.overall_hasher = D::new();
+        self.block_hasher = D::new();
         self.block_pos = 0;
     }
 }
 
-impl Input for DropboxContentHasher {
+impl<D: Digest + Clone + BlockInput + Input> Input for DropboxContentHasher<D> {
     fn input<B: AsRef<[u8]>>(&mut self, data: B) {
         let mut input = data.as_ref();
         while input.len() > 0 {
             if self.block_pos == BLOCK_SIZE {
                 let block_hasher = self.block_hasher.clone();
                 Input::input(&mut self.overall_hasher, block_hasher.result().as_slice());
-                self.block_hasher = Sha256::new();
+                self.block_hasher = D::new();
                 self.block_pos = 0;
             }
 
@@ -119,8 +131,8 @@ impl Input for DropboxContentHasher {
     }
 }
 
-impl FixedOutput for DropboxContentHasher {
-    type OutputSize = <Sha256 as FixedOutput>::OutputSize;
+impl<D: Digest + Clone + BlockInput + Input> FixedOutput for DropboxContentHasher<D> {
+    type OutputSize = D::OutputSize;
 
     fn fixed_result(mut self) -> GenericArray<u8, Self::OutputSize> {
         if self.block_pos > 0 {
@@ -130,8 +142,8 @@ impl FixedOutput for DropboxContentHasher {
     }
 }
 
-impl digest::BlockInput for DropboxContentHasher {
-    type BlockSize = U64;
+impl<D: Digest + Clone + BlockInput + Input> BlockInput for DropboxContentHasher<D> {
+    type BlockSize = D::BlockSize;
 }
 
 #[cfg(test)]
@@ -149,4 +161,23 @@ mod tests {
         let hex_hash = format!("{:x}", result);
         assert_eq!(hex_hash, expected);
     }
+
+    #[test]
+    fn test_vector_generic_sha256() {
+        let expected = "485291fa0ee50c016982abbfa943957bcd231aae0492ccbaa22c58e3997b35e0".to_string();
+        let mut file = File::open("test-data/milky-way-nasa.jpg").expect("Couldn't open test file");
+
+        let mut hasher: DropboxContentHasher<Sha256> = DropboxContentHasher::new();
+        let mut buf = vec![0; BLOCK_SIZE];
+        loop {
+            use std::io::Read as _;
+            let len = file.read(&mut buf).expect("read");
+            if len == 0 { break; }
+            Input::input(&mut hasher, &buf[..len]);
+        }
+        let result = hasher.result();
+
+        let hex_hash = format!("{:x}", result);
+        assert_eq!(hex_hash, expected);
+    }
 }
@@ -0,0 +1,119 @@
+//! Multihash-framed output for the Dropbox content hash.
+//!
+//! [Multihash](https://github.com/multiformats/multihash) prefixes a digest with a
+//! varint function code and a varint length so content-addressed stores (IPFS-style
+//! CAS, package lock files) can key blobs without assuming a fixed hash algorithm.
+//!
+//! Note this frames the Dropbox block-tree digest itself, not a multihash of the raw
+//! file bytes — the code/length prefix only describes the 32-byte SHA-256 output of
+//! `DropboxContentHasher`, which is already a hash-of-hashes, not a plain SHA-256 of
+//! the file.
+
+use std::io;
+use std::path::Path;
+
+use crate::DropboxContentHasher;
+
+/// The multihash function code for sha2-256, per the multihash table.
+const SHA2_256_CODE: u8 = 0x12;
+/// The multihash length field for a 32-byte digest.
+const SHA2_256_LEN: u8 = 32;
+
+/// Frame a raw 32-byte Dropbox content hash digest as a multihash: a varint
+/// hash-function code (`0x12` for sha2-256), a varint length (`32`), then the raw
+/// digest bytes. Both varints fit in a single byte for this digest, so the framing is
+/// just two prefix bytes followed by the digest.
+fn frame_multihash(digest: &[u8]) -> Vec<u8> {
+    let mut framed = Vec::with_capacity(2 + digest.len());
+    framed.push(SHA2_256_CODE);
+    framed.push(SHA2_256_LEN);
+    framed.extend_from_slice(digest);
+    framed
+}
+
+/// Lowercase-hex encode a multihash-framed byte buffer.
+fn hex_encode(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        s.push_str(&format!("{:02x}", byte));
+    }
+    s
+}
+
+impl DropboxContentHasher {
+    /// Frame an already-computed 32-byte content hash digest as a lowercase-hex
+    /// multihash: a `0x12` (sha2-256) function code, a `32` length byte, then the raw
+    /// digest. Use this to multihash-encode a digest obtained from any other path —
+    /// `hash_file_parallel`/`hash_slice_parallel`, `asyncio::HashingReader::finalize`,
+    /// or a plain `hash_file`/`hash_reader` result — without hand-rolling the prefix.
+    ///
+    /// Panics if `digest` isn't exactly 32 bytes: the `0x12 0x20` header only describes
+    /// a 32-byte sha2-256 digest, so framing anything else would silently produce a
+    /// multihash whose length byte lies about its body.
+    pub fn fixed_result_multihash(digest: &[u8]) -> String {
+        assert_eq!(
+            digest.len(),
+            SHA2_256_LEN as usize,
+            "fixed_result_multihash expects a {}-byte sha2-256 digest, got {} bytes",
+            SHA2_256_LEN,
+            digest.len()
+        );
+        hex_encode(&frame_multihash(digest))
+    }
+
+    /// Return the multihash-framed content hash for a given file: a `0x12` (sha2-256)
+    /// function code, a `32` length byte, then the raw digest, all lowercase-hex
+    /// encoded.
+    pub fn hash_file_multihash<T>(path: T) -> io::Result<String>
+    where
+        T: AsRef<Path>,
+    {
+        let digest = DropboxContentHasher::hash_file(path)?;
+        Ok(DropboxContentHasher::fixed_result_multihash(&digest))
+    }
+
+    /// Return the multihash-framed content hash for an object implementing `Read`.
+    pub fn hash_reader_multihash<T>(reader: T) -> io::Result<String>
+    where
+        T: io::Read,
+    {
+        let digest = DropboxContentHasher::hash_reader(reader)?;
+        Ok(DropboxContentHasher::fixed_result_multihash(&digest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn test_vector() {
+        // 0x12 (sha2-256) + 0x20 (32 bytes) prefixed onto the existing test vector digest.
+        let expected =
+            "1220485291fa0ee50c016982abbfa943957bcd231aae0492ccbaa22c58e3997b35e0".to_string();
+        let mut file = File::open("test-data/milky-way-nasa.jpg").expect("Couldn't open test file");
+
+        let result = DropboxContentHasher::hash_reader_multihash(&mut file)
+            .expect("Couldn't hash test file");
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn fixed_result_multihash_matches_hash_file_multihash() {
+        let digest = DropboxContentHasher::hash_file("test-data/milky-way-nasa.jpg")
+            .expect("Couldn't hash test file");
+
+        let expected = DropboxContentHasher::hash_file_multihash("test-data/milky-way-nasa.jpg")
+            .expect("Couldn't hash test file");
+
+        assert_eq!(DropboxContentHasher::fixed_result_multihash(&digest), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "expects a 32-byte sha2-256 digest")]
+    fn fixed_result_multihash_rejects_wrong_length() {
+        DropboxContentHasher::fixed_result_multihash(&[0u8; 48]);
+    }
+}